@@ -0,0 +1 @@
+pub mod muloom_gpu;
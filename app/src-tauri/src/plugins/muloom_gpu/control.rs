@@ -0,0 +1,125 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+
+use tauri::{command, AppHandle, Manager, Runtime, State};
+
+/// Presentation mode requested from the frontend, translated into a
+/// `wgpu::PresentMode` by the render thread once it next reconfigures.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PresentModeRequest {
+    Vsync,
+    Immediate,
+    Mailbox,
+}
+
+impl PresentModeRequest {
+    fn parse(value: &str) -> Result<Self, String> {
+        match value {
+            "vsync" => Ok(Self::Vsync),
+            "immediate" => Ok(Self::Immediate),
+            "mailbox" => Ok(Self::Mailbox),
+            other => Err(format!("unknown present mode: {other}")),
+        }
+    }
+
+    pub fn to_wgpu(self, supported: &[wgpu::PresentMode]) -> wgpu::PresentMode {
+        let preferred = match self {
+            Self::Vsync => wgpu::PresentMode::Fifo,
+            Self::Immediate => wgpu::PresentMode::Immediate,
+            Self::Mailbox => wgpu::PresentMode::Mailbox,
+        };
+        if supported.contains(&preferred) {
+            preferred
+        } else {
+            wgpu::PresentMode::Fifo
+        }
+    }
+}
+
+/// Shared state the webview can mutate and the render thread reads once per
+/// tick. Lives in Tauri managed state so command handlers and the render
+/// thread both reach it through `Arc<RenderControl>`.
+pub struct RenderControl {
+    clear_color: Mutex<wgpu::Color>,
+    present_mode: Mutex<PresentModeRequest>,
+    present_mode_dirty: AtomicBool,
+    paused: AtomicBool,
+}
+
+impl Default for RenderControl {
+    fn default() -> Self {
+        Self {
+            clear_color: Mutex::new(wgpu::Color {
+                r: 0.2,
+                g: 0.1,
+                b: 0.15,
+                a: 1.0,
+            }),
+            present_mode: Mutex::new(PresentModeRequest::Immediate),
+            present_mode_dirty: AtomicBool::new(false),
+            paused: AtomicBool::new(false),
+        }
+    }
+}
+
+impl RenderControl {
+    pub fn clear_color(&self) -> wgpu::Color {
+        *self.clear_color.lock().unwrap()
+    }
+
+    pub fn present_mode(&self) -> PresentModeRequest {
+        *self.present_mode.lock().unwrap()
+    }
+
+    /// Returns `true` exactly once after a new present mode is set, so the
+    /// render thread knows to reconfigure the surface.
+    pub fn take_present_mode_dirty(&self) -> bool {
+        self.present_mode_dirty.swap(false, Ordering::SeqCst)
+    }
+
+    pub fn is_paused(&self) -> bool {
+        self.paused.load(Ordering::SeqCst)
+    }
+}
+
+pub type SharedRenderControl = Arc<RenderControl>;
+
+#[command]
+pub fn set_clear_color(
+    control: State<'_, SharedRenderControl>,
+    r: f64,
+    g: f64,
+    b: f64,
+    a: f64,
+) -> Result<(), String> {
+    *control.clear_color.lock().unwrap() = wgpu::Color { r, g, b, a };
+    Ok(())
+}
+
+#[command]
+pub fn set_present_mode(control: State<'_, SharedRenderControl>, mode: String) -> Result<(), String> {
+    let parsed = PresentModeRequest::parse(&mode)?;
+    *control.present_mode.lock().unwrap() = parsed;
+    control.present_mode_dirty.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[command]
+pub fn pause(control: State<'_, SharedRenderControl>) -> Result<(), String> {
+    control.paused.store(true, Ordering::SeqCst);
+    Ok(())
+}
+
+#[command]
+pub fn resume(control: State<'_, SharedRenderControl>) -> Result<(), String> {
+    control.paused.store(false, Ordering::SeqCst);
+    Ok(())
+}
+
+/// Creates a fresh `RenderControl`, registers it as managed state on `app`,
+/// and returns the handle the render thread should be started with.
+pub fn install<R: Runtime>(app: &AppHandle<R>) -> SharedRenderControl {
+    let control: SharedRenderControl = Arc::new(RenderControl::default());
+    app.manage(control.clone());
+    control
+}
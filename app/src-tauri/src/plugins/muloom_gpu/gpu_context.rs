@@ -0,0 +1,141 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use tauri::{Runtime, WebviewWindow};
+use wgpu::{
+    Backends, DeviceDescriptor, Gles3MinorVersion, Instance, InstanceDescriptor, Surface,
+    SurfaceCapabilities, SurfaceConfiguration, TextureFormat, TextureUsages,
+};
+
+use super::control::PresentModeRequest;
+
+/// Number of frames the renderer is allowed to have in flight before it must
+/// wait on the GPU, matched against `desired_maximum_frame_latency`.
+pub(super) const FRAMES_IN_FLIGHT: u32 = 2;
+
+/// Everything `run_renderer` needs to drive a frame: the live surface, the
+/// device/queue it was created from, and the capabilities used to pick
+/// format/present mode/alpha mode. Rebuilt wholesale by
+/// [`create_gpu_context`] whenever the device is lost.
+pub struct GpuContext {
+    pub surface: Surface<'static>,
+    pub device: Arc<wgpu::Device>,
+    pub queue: Arc<wgpu::Queue>,
+    pub config: SurfaceConfiguration,
+    pub capabilities: SurfaceCapabilities,
+    pub surface_format: TextureFormat,
+    /// Flipped by the device-lost callback registered in [`create_gpu_context`];
+    /// `run_renderer` polls it to decide when to rebuild the context.
+    pub device_lost: Arc<AtomicBool>,
+}
+
+/// Builds the full GPU stack from scratch: instance -> surface -> adapter ->
+/// device/queue -> surface configuration. Called once at startup and again
+/// by the recovery path after a device loss, always re-requesting the
+/// adapter and re-deriving the surface from the still-live webview window
+/// rather than reusing anything from the previous attempt.
+pub fn create_gpu_context<R: Runtime>(
+    window: &WebviewWindow<R>,
+    present_mode: PresentModeRequest,
+) -> Result<GpuContext> {
+    let instance = Instance::new(InstanceDescriptor {
+        backends: select_backends(),
+        flags: wgpu::InstanceFlags::default(),
+        dx12_shader_compiler: Default::default(),
+        gles_minor_version: Gles3MinorVersion::Automatic,
+    });
+
+    // `from_window` derives the correct raw surface handle for whichever
+    // platform the webview window lives on (CAMetalLayer, HWND, Wayland/X11).
+    let surface_target = unsafe { wgpu::SurfaceTargetUnsafe::from_window(window) }
+        .context("failed to derive surface target from Tauri window")?;
+    let surface = unsafe { instance.create_surface_unsafe(surface_target) }
+        .context("failed to create wgpu surface from Tauri window")?;
+
+    let adapter = pollster::block_on(instance.request_adapter(&wgpu::RequestAdapterOptions {
+        power_preference: wgpu::PowerPreference::HighPerformance,
+        compatible_surface: Some(&surface),
+        force_fallback_adapter: false,
+    }))
+    .context("failed to acquire GPU adapter")?;
+
+    let (device, queue) = pollster::block_on(adapter.request_device(
+        &DeviceDescriptor {
+            label: Some("muloom_gpu_device"),
+            required_features: wgpu::Features::empty(),
+            required_limits: wgpu::Limits::default(),
+        },
+        None,
+    ))
+    .context("failed to create GPU device")?;
+
+    let device_lost = Arc::new(AtomicBool::new(false));
+    {
+        let device_lost = device_lost.clone();
+        device.set_device_lost_callback(move |reason, message| {
+            eprintln!("muloom_gpu: device lost ({reason:?}): {message}");
+            device_lost.store(true, Ordering::SeqCst);
+        });
+    }
+
+    let capabilities = surface.get_capabilities(&adapter);
+    let surface_format = pick_surface_format(&capabilities.formats);
+    let resolved_present_mode = present_mode.to_wgpu(&capabilities.present_modes);
+
+    let alpha_mode = capabilities
+        .alpha_modes
+        .iter()
+        .copied()
+        .find(|mode| matches!(mode, wgpu::CompositeAlphaMode::Opaque | wgpu::CompositeAlphaMode::Auto))
+        .unwrap_or(wgpu::CompositeAlphaMode::Auto);
+
+    let config = SurfaceConfiguration {
+        usage: TextureUsages::RENDER_ATTACHMENT,
+        format: surface_format,
+        width: 1,
+        height: 1,
+        present_mode: resolved_present_mode,
+        alpha_mode,
+        view_formats: vec![],
+        desired_maximum_frame_latency: FRAMES_IN_FLIGHT,
+    };
+
+    Ok(GpuContext {
+        surface,
+        device: Arc::new(device),
+        queue: Arc::new(queue),
+        config,
+        capabilities,
+        surface_format,
+        device_lost,
+    })
+}
+
+/// GPU backend selected for the current target platform.
+fn select_backends() -> Backends {
+    #[cfg(target_os = "macos")]
+    {
+        Backends::METAL
+    }
+    #[cfg(target_os = "windows")]
+    {
+        Backends::DX12
+    }
+    #[cfg(target_os = "linux")]
+    {
+        Backends::VULKAN | Backends::GL
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        Backends::PRIMARY
+    }
+}
+
+fn pick_surface_format(formats: &[TextureFormat]) -> TextureFormat {
+    formats
+        .iter()
+        .copied()
+        .find(|format| matches!(format, TextureFormat::Bgra8UnormSrgb | TextureFormat::Bgra8Unorm))
+        .unwrap_or_else(|| formats.get(0).copied().unwrap_or(TextureFormat::Bgra8Unorm))
+}
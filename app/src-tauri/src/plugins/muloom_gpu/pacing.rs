@@ -0,0 +1,46 @@
+use std::thread;
+use std::time::{Duration, Instant};
+
+use wgpu::PresentMode;
+
+/// Frame rate enforced when the surface isn't running a vsync-coupled
+/// present mode (Immediate/Mailbox), so an uncapped GPU doesn't spin.
+const DEFAULT_TARGET_FPS: f32 = 120.0;
+
+/// Paces the render loop so it neither busy-spins nor oversleeps.
+///
+/// Vsync-style present modes (Fifo/FifoRelaxed/AutoVsync) do nothing here:
+/// `surface.get_current_texture()`/`frame.present()` already block on the
+/// compositor's vsync for those modes, so there's nothing left to wait on.
+/// A `CVDisplayLink`-backed wakeup would let the thread skip that blocking
+/// call entirely, but this crate doesn't carry Core Video FFI bindings yet,
+/// so that's left for a future pass instead of shipping unreachable
+/// scaffolding. Everything else sleeps only the remainder of a fixed frame
+/// budget computed from the current frame's start time.
+pub struct FramePacer {
+    target_frame_duration: Duration,
+}
+
+impl FramePacer {
+    pub fn new() -> Self {
+        Self {
+            target_frame_duration: Duration::from_secs_f32(1.0 / DEFAULT_TARGET_FPS),
+        }
+    }
+
+    /// Called once per loop iteration after presenting. `frame_start` is the
+    /// `Instant` recorded just before this frame's work began.
+    pub fn pace(&self, present_mode: PresentMode, frame_start: Instant) {
+        match present_mode {
+            PresentMode::Fifo | PresentMode::FifoRelaxed | PresentMode::AutoVsync => {
+                // `present()` already blocked until the next vsync.
+            }
+            _ => {
+                let elapsed = frame_start.elapsed();
+                if elapsed < self.target_frame_duration {
+                    thread::sleep(self.target_frame_duration - elapsed);
+                }
+            }
+        }
+    }
+}
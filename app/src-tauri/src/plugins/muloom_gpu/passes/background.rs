@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use crate::plugins::muloom_gpu::control::SharedRenderControl;
+use crate::plugins::muloom_gpu::pipeline::BackgroundPipeline;
+use crate::plugins::muloom_gpu::render_graph::{FrameContext, RenderPass};
+
+/// Built-in `Background` phase pass: a clear to the frontend-controlled color
+/// followed by the animated fullscreen-triangle shader.
+pub struct BackgroundPass {
+    control: SharedRenderControl,
+    pipeline: BackgroundPipeline,
+    queue: Arc<wgpu::Queue>,
+}
+
+impl BackgroundPass {
+    pub fn new(
+        device: &wgpu::Device,
+        queue: Arc<wgpu::Queue>,
+        surface_format: wgpu::TextureFormat,
+        control: SharedRenderControl,
+    ) -> Self {
+        Self {
+            control,
+            pipeline: BackgroundPipeline::new(device, surface_format),
+            queue,
+        }
+    }
+}
+
+impl RenderPass for BackgroundPass {
+    fn record(&self, ctx: &FrameContext, encoder: &mut wgpu::CommandEncoder) {
+        let color = self.control.clear_color();
+        self.pipeline.update(
+            &self.queue,
+            ctx.elapsed,
+            ctx.config.width,
+            ctx.config.height,
+            color,
+        );
+
+        let mut pass = encoder.begin_render_pass(&wgpu::RenderPassDescriptor {
+            label: Some("muloom_gpu_background_pass"),
+            color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                view: ctx.view,
+                resolve_target: None,
+                ops: wgpu::Operations {
+                    load: wgpu::LoadOp::Clear(color),
+                    store: wgpu::StoreOp::Store,
+                },
+            })],
+            depth_stencil_attachment: None,
+            timestamp_writes: None,
+            occlusion_query_set: None,
+        });
+        self.pipeline.draw(&mut pass);
+    }
+}
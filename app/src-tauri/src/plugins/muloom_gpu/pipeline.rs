@@ -0,0 +1,134 @@
+use wgpu::util::DeviceExt;
+
+const SHADER_SOURCE: &str = include_str!("shaders/background.wgsl");
+
+/// Time, viewport, and frontend-controlled clear color uploaded to the
+/// background shader each frame. Field order/size mirrors the WGSL
+/// `Uniforms` struct; the explicit `_padding` after the three leading scalars
+/// puts `clear_color` at the 16-byte offset a `vec4<f32>` requires.
+#[repr(C)]
+#[derive(Copy, Clone, bytemuck::Pod, bytemuck::Zeroable)]
+struct Uniforms {
+    time: f32,
+    width: f32,
+    height: f32,
+    _padding: f32,
+    clear_color: [f32; 4],
+}
+
+/// Fullscreen-triangle shader stage that replaces the flat clear color with
+/// an actual draw call. Later passes can follow the same
+/// shader-module + uniform-buffer + bind-group shape.
+pub struct BackgroundPipeline {
+    pipeline: wgpu::RenderPipeline,
+    uniform_buffer: wgpu::Buffer,
+    bind_group: wgpu::BindGroup,
+}
+
+impl BackgroundPipeline {
+    pub fn new(device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+        let shader = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+            label: Some("muloom_gpu_background_shader"),
+            source: wgpu::ShaderSource::Wgsl(SHADER_SOURCE.into()),
+        });
+
+        let uniform_buffer = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: Some("muloom_gpu_background_uniforms"),
+            contents: bytemuck::bytes_of(&Uniforms {
+                time: 0.0,
+                width: 1.0,
+                height: 1.0,
+                _padding: 0.0,
+                clear_color: [0.2, 0.1, 0.15, 1.0],
+            }),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
+
+        let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
+            label: Some("muloom_gpu_background_bind_group_layout"),
+            entries: &[wgpu::BindGroupLayoutEntry {
+                binding: 0,
+                visibility: wgpu::ShaderStages::FRAGMENT,
+                ty: wgpu::BindingType::Buffer {
+                    ty: wgpu::BufferBindingType::Uniform,
+                    has_dynamic_offset: false,
+                    min_binding_size: None,
+                },
+                count: None,
+            }],
+        });
+
+        let bind_group = device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: Some("muloom_gpu_background_bind_group"),
+            layout: &bind_group_layout,
+            entries: &[wgpu::BindGroupEntry {
+                binding: 0,
+                resource: uniform_buffer.as_entire_binding(),
+            }],
+        });
+
+        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+            label: Some("muloom_gpu_background_pipeline_layout"),
+            bind_group_layouts: &[&bind_group_layout],
+            push_constant_ranges: &[],
+        });
+
+        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+            label: Some("muloom_gpu_background_pipeline"),
+            layout: Some(&pipeline_layout),
+            vertex: wgpu::VertexState {
+                module: &shader,
+                entry_point: "vs_main",
+                buffers: &[],
+            },
+            fragment: Some(wgpu::FragmentState {
+                module: &shader,
+                entry_point: "fs_main",
+                targets: &[Some(wgpu::ColorTargetState {
+                    format: surface_format,
+                    blend: Some(wgpu::BlendState::REPLACE),
+                    write_mask: wgpu::ColorWrites::ALL,
+                })],
+            }),
+            primitive: wgpu::PrimitiveState::default(),
+            depth_stencil: None,
+            multisample: wgpu::MultisampleState::default(),
+            multiview: None,
+        });
+
+        Self {
+            pipeline,
+            uniform_buffer,
+            bind_group,
+        }
+    }
+
+    pub fn update(
+        &self,
+        queue: &wgpu::Queue,
+        time: f32,
+        width: u32,
+        height: u32,
+        clear_color: wgpu::Color,
+    ) {
+        let uniforms = Uniforms {
+            time,
+            width: width as f32,
+            height: height as f32,
+            _padding: 0.0,
+            clear_color: [
+                clear_color.r as f32,
+                clear_color.g as f32,
+                clear_color.b as f32,
+                clear_color.a as f32,
+            ],
+        };
+        queue.write_buffer(&self.uniform_buffer, 0, bytemuck::bytes_of(&uniforms));
+    }
+
+    pub fn draw<'pass>(&'pass self, pass: &mut wgpu::RenderPass<'pass>) {
+        pass.set_pipeline(&self.pipeline);
+        pass.set_bind_group(0, &self.bind_group, &[]);
+        pass.draw(0..3, 0..1);
+    }
+}
@@ -0,0 +1,42 @@
+use std::thread;
+use std::time::Duration;
+
+use anyhow::Result;
+use tauri::{Runtime, WebviewWindow};
+
+use super::control::PresentModeRequest;
+use super::gpu_context::{create_gpu_context, GpuContext};
+
+const MAX_RECOVERY_ATTEMPTS: u32 = 5;
+const INITIAL_BACKOFF: Duration = Duration::from_millis(250);
+
+/// Rebuilds the entire GPU context (instance -> surface -> adapter -> device)
+/// from scratch, with bounded exponential backoff between attempts. Used
+/// after an unrecoverable error — device loss, or repeated `Lost` surfaces
+/// that a plain reconfigure didn't fix — to bring rendering back instead of
+/// leaving the window permanently black.
+pub fn recreate_with_backoff<R: Runtime>(
+    window: &WebviewWindow<R>,
+    present_mode: PresentModeRequest,
+) -> Result<GpuContext> {
+    let mut backoff = INITIAL_BACKOFF;
+    let mut last_err = None;
+
+    for attempt in 1..=MAX_RECOVERY_ATTEMPTS {
+        match create_gpu_context(window, present_mode) {
+            Ok(ctx) => return Ok(ctx),
+            Err(err) => {
+                eprintln!(
+                    "muloom_gpu: GPU context recovery attempt {attempt}/{MAX_RECOVERY_ATTEMPTS} failed: {err:?}"
+                );
+                last_err = Some(err);
+                if attempt < MAX_RECOVERY_ATTEMPTS {
+                    thread::sleep(backoff);
+                    backoff *= 2;
+                }
+            }
+        }
+    }
+
+    Err(last_err.unwrap_or_else(|| anyhow::anyhow!("GPU context recovery failed")))
+}
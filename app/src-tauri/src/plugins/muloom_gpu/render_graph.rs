@@ -0,0 +1,87 @@
+/// Per-frame data handed to every registered [`RenderPass`]. Borrowed for the
+/// duration of a single `record` call, so passes cannot stash it across frames.
+pub struct FrameContext<'a> {
+    pub view: &'a wgpu::TextureView,
+    pub config: &'a wgpu::SurfaceConfiguration,
+    pub frame_index: u64,
+    pub elapsed: f32,
+    /// Snapshot of `window_state::WindowState` bits for this frame, so a
+    /// pass can adapt to e.g. fullscreen without owning the window itself.
+    pub window_state: u32,
+}
+
+/// One stage of the render graph. Implementors record their own commands
+/// into the shared encoder; the `Renderer` owns ordering and submission.
+pub trait RenderPass: Send + Sync {
+    fn record(&self, ctx: &FrameContext, encoder: &mut wgpu::CommandEncoder);
+}
+
+/// Coarse ordering bucket a pass is registered under. Passes within the same
+/// phase run in registration order; phases themselves always run in the
+/// order listed here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Phase {
+    Background,
+    Opaque,
+    Overlay,
+}
+
+const PHASE_ORDER: [Phase; 3] = [Phase::Background, Phase::Opaque, Phase::Overlay];
+
+/// Owns the GPU handles and the ordered set of passes that make up a frame.
+/// `run_renderer` acquires the surface texture and calls [`Renderer::render`]
+/// once per tick; everything else is composed by registering passes.
+pub struct Renderer {
+    device: std::sync::Arc<wgpu::Device>,
+    queue: std::sync::Arc<wgpu::Queue>,
+    passes: Vec<(Phase, Box<dyn RenderPass>)>,
+}
+
+impl Renderer {
+    pub fn new(device: std::sync::Arc<wgpu::Device>, queue: std::sync::Arc<wgpu::Queue>) -> Self {
+        Self {
+            device,
+            queue,
+            passes: Vec::new(),
+        }
+    }
+
+    pub fn add_pass(&mut self, phase: Phase, pass: Box<dyn RenderPass>) {
+        self.passes.push((phase, pass));
+    }
+
+    /// Records every registered pass into one shared encoder, in phase
+    /// order, and submits it as a single command buffer.
+    pub fn render(
+        &self,
+        view: &wgpu::TextureView,
+        config: &wgpu::SurfaceConfiguration,
+        frame_index: u64,
+        elapsed: f32,
+        window_state: u32,
+    ) {
+        let ctx = FrameContext {
+            view,
+            config,
+            frame_index,
+            elapsed,
+            window_state,
+        };
+
+        let mut encoder = self
+            .device
+            .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                label: Some("muloom_gpu_frame_encoder"),
+            });
+
+        for phase in PHASE_ORDER {
+            for (pass_phase, pass) in &self.passes {
+                if *pass_phase == phase {
+                    pass.record(&ctx, &mut encoder);
+                }
+            }
+        }
+
+        self.queue.submit(Some(encoder.finish()));
+    }
+}
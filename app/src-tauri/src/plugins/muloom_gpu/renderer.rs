@@ -0,0 +1,259 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use tauri::Manager;
+use tauri::{AppHandle, PhysicalSize, Runtime, WebviewWindow, WindowEvent};
+
+use super::control::SharedRenderControl;
+use super::gpu_context::{create_gpu_context, GpuContext};
+use super::pacing::FramePacer;
+use super::passes::background::BackgroundPass;
+use super::recovery;
+use super::render_graph::{Phase, Renderer};
+use super::window_state::{self, WindowState};
+
+/// Consecutive `Lost`/`Outdated` surfaces a plain reconfigure is allowed to
+/// absorb before the renderer treats it as unrecoverable and rebuilds the
+/// whole GPU context instead.
+const MAX_CONSECUTIVE_SURFACE_LOSSES: u32 = 3;
+
+/// Name given to the dedicated render thread, kept distinct per backend so
+/// platform-specific stack traces and profiler captures are easy to tell apart.
+fn render_thread_name() -> &'static str {
+    #[cfg(target_os = "macos")]
+    {
+        "muloom-metal-renderer"
+    }
+    #[cfg(target_os = "windows")]
+    {
+        "muloom-dx12-renderer"
+    }
+    #[cfg(target_os = "linux")]
+    {
+        "muloom-vulkan-renderer"
+    }
+    #[cfg(not(any(target_os = "macos", target_os = "windows", target_os = "linux")))]
+    {
+        "muloom-gpu-renderer"
+    }
+}
+
+pub fn start_renderer<R: Runtime>(app: &AppHandle<R>, control: SharedRenderControl) -> Result<()> {
+    let window = if let Some(found) = app.get_webview_window("main") {
+        found
+    } else if let Some(first) = app.webview_windows().values().next().cloned() {
+        first
+    } else {
+        return Err(anyhow::anyhow!(
+            "failed to locate webview window for GPU renderer"
+        ));
+    };
+
+    let running = Arc::new(AtomicBool::new(true));
+    let window_resized = Arc::new(AtomicBool::new(true));
+    let window_state = WindowState::new();
+    window_state.refresh(&window);
+
+    {
+        let running = running.clone();
+        let window_resized = window_resized.clone();
+        let window_state = window_state.clone();
+        let state_window = window.clone();
+        window.on_window_event(move |event| match event {
+            WindowEvent::Destroyed => {
+                running.store(false, Ordering::SeqCst);
+            }
+            WindowEvent::CloseRequested { .. } => {
+                running.store(false, Ordering::SeqCst);
+            }
+            WindowEvent::Resized(_) => {
+                window_resized.store(true, Ordering::SeqCst);
+                window_state.refresh(&state_window);
+            }
+            WindowEvent::ScaleFactorChanged { .. } => {
+                window_resized.store(true, Ordering::SeqCst);
+            }
+            WindowEvent::Focused(_) => {
+                window_state.refresh(&state_window);
+            }
+            _ => {}
+        });
+    }
+
+    thread::Builder::new()
+        .name(render_thread_name().into())
+        .spawn({
+            let window = window.clone();
+            move || {
+                if let Err(err) =
+                    run_renderer::<R>(window, running, window_resized, window_state, control)
+                {
+                    eprintln!("muloom_gpu: renderer stopped: {err:?}");
+                }
+            }
+        })?;
+
+    Ok(())
+}
+
+/// Builds the render graph for a (newly created or recovered) `GpuContext`.
+/// Kept as a function so the recovery path can rebuild the graph the same
+/// way startup does, instead of duplicating the pass list.
+fn build_renderer(ctx: &GpuContext, control: SharedRenderControl) -> Renderer {
+    let mut renderer = Renderer::new(ctx.device.clone(), ctx.queue.clone());
+    renderer.add_pass(
+        Phase::Background,
+        Box::new(BackgroundPass::new(
+            &ctx.device,
+            ctx.queue.clone(),
+            ctx.surface_format,
+            control,
+        )),
+    );
+    renderer
+}
+
+fn run_renderer<R: Runtime>(
+    window: WebviewWindow<R>,
+    running: Arc<AtomicBool>,
+    window_resized: Arc<AtomicBool>,
+    window_state: Arc<WindowState>,
+    control: SharedRenderControl,
+) -> Result<()> {
+    use wgpu::SurfaceError;
+
+    let mut ctx = create_gpu_context(&window, control.present_mode())?;
+    resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, true)?;
+    let mut renderer = build_renderer(&ctx, control.clone());
+
+    let pacer = FramePacer::new();
+
+    let mut frame_index: u64 = 0;
+    let start_time = Instant::now();
+    let mut consecutive_surface_losses: u32 = 0;
+
+    while running.load(Ordering::SeqCst) {
+        let frame_start = Instant::now();
+
+        if ctx.device_lost.load(Ordering::SeqCst) {
+            match recovery::recreate_with_backoff(&window, control.present_mode()) {
+                Ok(new_ctx) => {
+                    ctx = new_ctx;
+                    resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, true)?;
+                    renderer = build_renderer(&ctx, control.clone());
+                    consecutive_surface_losses = 0;
+                }
+                Err(err) => {
+                    return Err(err.context("GPU device lost and recovery exhausted its retries"));
+                }
+            }
+        }
+
+        if control.take_present_mode_dirty() {
+            ctx.config.present_mode = control
+                .present_mode()
+                .to_wgpu(&ctx.capabilities.present_modes);
+            resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, true)?;
+        }
+
+        let wm_constrained = window_state.contains(window_state::MAXIMIZED);
+
+        if window_resized.swap(false, Ordering::SeqCst) {
+            resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, false)?;
+        } else if !wm_constrained {
+            // Poll window size in case of missed events. Skipped when the WM
+            // already constrains the size (maximized): it can't change
+            // without an explicit resize event in that state.
+            resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, false)?;
+        }
+
+        if window_state.contains(window_state::HIDDEN) {
+            // Nothing to present while hidden/minimized; avoid spinning on
+            // `get_current_texture` for a surface nobody can see.
+            thread::sleep(Duration::from_millis(100));
+            continue;
+        }
+
+        if ctx.config.width == 0 || ctx.config.height == 0 {
+            thread::sleep(Duration::from_millis(50));
+            continue;
+        }
+
+        if control.is_paused() {
+            thread::sleep(Duration::from_millis(16));
+            continue;
+        }
+
+        match ctx.surface.get_current_texture() {
+            Ok(frame) => {
+                consecutive_surface_losses = 0;
+
+                let view = frame
+                    .texture
+                    .create_view(&wgpu::TextureViewDescriptor::default());
+
+                renderer.render(
+                    &view,
+                    &ctx.config,
+                    frame_index,
+                    start_time.elapsed().as_secs_f32(),
+                    window_state.snapshot(),
+                );
+                frame.present();
+            }
+            Err(SurfaceError::Timeout) => {
+                // No frame available yet; skip this tick.
+            }
+            Err(err @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
+                consecutive_surface_losses += 1;
+                if consecutive_surface_losses > MAX_CONSECUTIVE_SURFACE_LOSSES {
+                    eprintln!(
+                        "muloom_gpu: surface kept reporting {err:?} after {MAX_CONSECUTIVE_SURFACE_LOSSES} reconfigures; rebuilding GPU context"
+                    );
+                    ctx = recovery::recreate_with_backoff(&window, control.present_mode())?;
+                    resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, true)?;
+                    renderer = build_renderer(&ctx, control.clone());
+                    consecutive_surface_losses = 0;
+                } else {
+                    resize_surface(&window, &ctx.surface, &ctx.device, &mut ctx.config, true)?;
+                }
+            }
+            Err(SurfaceError::OutOfMemory) => {
+                running.store(false, Ordering::SeqCst);
+                break;
+            }
+        }
+
+        frame_index = frame_index.wrapping_add(1);
+
+        pacer.pace(ctx.config.present_mode, frame_start);
+    }
+
+    Ok(())
+}
+
+fn resize_surface<R: Runtime>(
+    window: &WebviewWindow<R>,
+    surface: &wgpu::Surface<'static>,
+    device: &wgpu::Device,
+    config: &mut wgpu::SurfaceConfiguration,
+    force: bool,
+) -> Result<()> {
+    let size = window
+        .inner_size()
+        .unwrap_or_else(|_| PhysicalSize::new(config.width, config.height));
+
+    let width = size.width.max(1);
+    let height = size.height.max(1);
+
+    if force || width != config.width || height != config.height {
+        config.width = width;
+        config.height = height;
+        surface.configure(device, config);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,53 @@
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::Arc;
+
+use tauri::{Runtime, WebviewWindow};
+
+pub const MAXIMIZED: u32 = 1 << 0;
+pub const FULLSCREEN: u32 = 1 << 1;
+pub const HIDDEN: u32 = 1 << 2;
+
+/// Bitfield tracking how the window manager currently presents the window,
+/// shared between the `on_window_event` closure and the render loop so the
+/// loop can tell a plain resize apart from entering fullscreen, being
+/// maximized, or hidden (`is_visible()` reporting false).
+///
+/// A `TILED` bit (for tiling compositors such as GNOME/KDE/Windows snap) was
+/// considered but dropped: Tauri's `WebviewWindow` has no query for it, and
+/// there's no portable way to detect it without per-compositor integration,
+/// so a flag that could never actually be set isn't shipped here.
+pub struct WindowState {
+    bits: AtomicU32,
+}
+
+impl WindowState {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self {
+            bits: AtomicU32::new(0),
+        })
+    }
+
+    pub fn snapshot(&self) -> u32 {
+        self.bits.load(Ordering::SeqCst)
+    }
+
+    pub fn contains(&self, flag: u32) -> bool {
+        self.snapshot() & flag != 0
+    }
+
+    fn set(&self, flag: u32, on: bool) {
+        if on {
+            self.bits.fetch_or(flag, Ordering::SeqCst);
+        } else {
+            self.bits.fetch_and(!flag, Ordering::SeqCst);
+        }
+    }
+
+    /// Re-reads maximized/fullscreen/visibility from the window and updates
+    /// the bitfield accordingly.
+    pub fn refresh<R: Runtime>(&self, window: &WebviewWindow<R>) {
+        self.set(MAXIMIZED, window.is_maximized().unwrap_or(false));
+        self.set(FULLSCREEN, window.is_fullscreen().unwrap_or(false));
+        self.set(HIDDEN, !window.is_visible().unwrap_or(true));
+    }
+}